@@ -0,0 +1,73 @@
+use core::fmt;
+
+use {Future, Poll};
+
+/// Future for the `max_polls` combinator, failing a future which hasn't
+/// completed within a fixed number of polls.
+///
+/// This is created by the `Future::max_polls` method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct MaxPolls<A: Future> {
+    future: A,
+    remaining: usize,
+}
+
+pub fn new<A: Future>(future: A, max: usize) -> MaxPolls<A> {
+    MaxPolls {
+        future: future,
+        remaining: max,
+    }
+}
+
+impl<A: Future> Future for MaxPolls<A> {
+    type Item = A::Item;
+    type Error = MaxPollsError<A::Error>;
+
+    fn poll(&mut self) -> Poll<A::Item, MaxPollsError<A::Error>> {
+        if self.remaining == 0 {
+            return Err(MaxPollsError::PollBudgetExceeded)
+        }
+        self.remaining -= 1;
+        self.future.poll().map_err(MaxPollsError::Inner)
+    }
+}
+
+/// Error returned from a `MaxPolls` future.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MaxPollsError<E> {
+    /// The underlying future resolved with an error.
+    Inner(E),
+    /// The underlying future was polled the maximum number of times without
+    /// completing.
+    PollBudgetExceeded,
+}
+
+impl<E: fmt::Display> fmt::Display for MaxPollsError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MaxPollsError::Inner(ref e) => e.fmt(fmt),
+            MaxPollsError::PollBudgetExceeded => write!(fmt, "poll budget exceeded"),
+        }
+    }
+}
+
+if_std! {
+    use std::error::Error;
+
+    impl<E: Error> Error for MaxPollsError<E> {
+        fn description(&self) -> &str {
+            match *self {
+                MaxPollsError::Inner(ref e) => e.description(),
+                MaxPollsError::PollBudgetExceeded => "poll budget exceeded",
+            }
+        }
+
+        fn cause(&self) -> Option<&Error> {
+            match *self {
+                MaxPollsError::Inner(ref e) => e.cause(),
+                MaxPollsError::PollBudgetExceeded => None,
+            }
+        }
+    }
+}
@@ -57,6 +57,7 @@ mod select2;
 mod then;
 mod either;
 mod inspect;
+mod max_polls;
 
 // impl details
 mod chain;
@@ -76,6 +77,7 @@ pub use self::select2::Select2;
 pub use self::then::Then;
 pub use self::either::Either;
 pub use self::inspect::Inspect;
+pub use self::max_polls::{MaxPolls, MaxPollsError};
 
 if_std! {
     mod catch_unwind;
@@ -926,6 +928,38 @@ pub trait Future {
         assert_future::<Self::Item, Self::Error, _>(inspect::new(self, f))
     }
 
+    /// Fail this future if it is polled more than `max` times without
+    /// completing.
+    ///
+    /// The underlying future is polled at most `max` times. If it still hasn't
+    /// resolved by then, the next call to `poll` returns
+    /// `MaxPollsError::PollBudgetExceeded` instead of polling it again. Errors
+    /// from the underlying future are passed through as `MaxPollsError::Inner`.
+    ///
+    /// This is intended as a defensive guard around futures which are expected
+    /// to complete quickly but may occasionally livelock. The count is never
+    /// reset, so it bounds the total number of polls over the future's
+    /// lifetime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::prelude::*;
+    /// use futures::future::{self, MaxPollsError};
+    ///
+    /// let future = future::ok::<u32, u32>(1).max_polls(1);
+    /// assert_eq!(future.wait(), Ok(1));
+    ///
+    /// let future = future::empty::<u32, u32>().max_polls(0);
+    /// assert_eq!(future.wait(), Err(MaxPollsError::PollBudgetExceeded));
+    /// ```
+    fn max_polls(self, max: usize) -> MaxPolls<Self>
+        where Self: Sized
+    {
+        let f = max_polls::new(self, max);
+        assert_future::<Self::Item, MaxPollsError<Self::Error>, _>(f)
+    }
+
     /// Catches unwinding panics while polling the future.
     ///
     /// In general, panics within a future can propagate all the way out to the
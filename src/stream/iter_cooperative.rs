@@ -0,0 +1,82 @@
+use core::marker;
+
+use {Async, Poll};
+use stream::Stream;
+use task;
+
+/// A stream which is a shim over an underlying instance of `Iterator`, yielding
+/// control to the executor after each batch of items.
+///
+/// This is created by the `stream::iter_cooperative` function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct IterCooperative<I: Iterator, E> {
+    iter: I,
+    batch: usize,
+    yielded: usize,
+    next: Option<I::Item>,
+    _marker: marker::PhantomData<fn() -> E>,
+}
+
+/// Converts an `Iterator` into a `Stream` which yields control back to the
+/// executor after every `batch` items.
+///
+/// Unlike `iter_ok`, which is always ready, this stream returns `NotReady`
+/// once `batch` items have been produced, after notifying the current task so
+/// it's polled again. This lets an iterator whose `next` is expensive be
+/// consumed without starving other futures running on the same executor.
+///
+/// Before yielding, the stream takes the next item from the iterator and holds
+/// it until it's polled again. That way a stream whose last batch is full
+/// finishes right away instead of yielding once more first.
+///
+/// Note that as this stream calls `task::current`, it must be polled from
+/// within a task.
+///
+/// # Panics
+///
+/// This function panics if `batch` is zero.
+///
+/// ```rust
+/// use futures::*;
+///
+/// let stream = stream::iter_cooperative::<_, ()>(vec![17, 19], 1);
+/// assert_eq!(stream.collect().wait(), Ok(vec![17, 19]));
+/// ```
+pub fn iter_cooperative<I, E>(i: I, batch: usize) -> IterCooperative<I::IntoIter, E>
+    where I: IntoIterator,
+{
+    assert!(batch > 0);
+
+    IterCooperative {
+        iter: i.into_iter(),
+        batch: batch,
+        yielded: 0,
+        next: None,
+        _marker: marker::PhantomData,
+    }
+}
+
+impl<I, E> Stream for IterCooperative<I, E>
+    where I: Iterator,
+{
+    type Item = I::Item;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll<Option<I::Item>, E> {
+        if let Some(item) = self.next.take() {
+            self.yielded = 1;
+            return Ok(Async::Ready(Some(item)))
+        }
+        if self.yielded == self.batch {
+            match self.iter.next() {
+                Some(item) => self.next = Some(item),
+                None => return Ok(Async::Ready(None)),
+            }
+            task::current().notify();
+            return Ok(Async::NotReady)
+        }
+        self.yielded += 1;
+        Ok(Async::Ready(self.iter.next()))
+    }
+}
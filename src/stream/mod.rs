@@ -27,6 +27,8 @@ mod iter_ok;
 pub use self::iter_ok::{iter_ok, IterOk};
 mod iter_result;
 pub use self::iter_result::{iter_result, IterResult};
+mod iter_cooperative;
+pub use self::iter_cooperative::{iter_cooperative, IterCooperative};
 
 mod repeat;
 pub use self::repeat::{repeat, Repeat};
@@ -0,0 +1,225 @@
+//! A futures-aware mutable memory location
+//!
+//! This is a single-threaded counterpart to `sync::BiLock` for any number of
+//! owners on the same thread.
+
+use std::cell::{RefCell, UnsafeCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::prelude::v1::*;
+use std::rc::Rc;
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A shared, mutable memory location whose exclusive borrows are acquired
+/// through futures.
+///
+/// Sharing state between tasks with `Rc<RefCell<T>>` panics if one task holds
+/// a borrow while it's blocked and another task tries to borrow as well. An
+/// `AsyncCell` instead hands out borrows through the `BorrowMut` future, which
+/// resolves once the previous borrow has been released. Tasks waiting for a
+/// borrow are granted it in the order they first polled `BorrowMut`.
+///
+/// Cloning an `AsyncCell` returns another handle to the same value. Handles
+/// cannot be sent across threads.
+pub struct AsyncCell<T> {
+    inner: Rc<Inner<T>>,
+}
+
+struct Inner<T> {
+    value: UnsafeCell<T>,
+    state: RefCell<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    // Whether the value is currently borrowed, or has been handed off to the
+    // waiter in `granted` which hasn't picked it up yet.
+    borrowed: bool,
+    granted: Option<usize>,
+    waiters: VecDeque<(usize, Task)>,
+    next_id: usize,
+}
+
+/// A future which resolves to an `AsyncCellGuard` once the value in an
+/// `AsyncCell` is available to be borrowed.
+///
+/// This is created by the `AsyncCell::borrow_mut` method.
+#[must_use = "futures do nothing unless polled"]
+pub struct BorrowMut<T> {
+    inner: Rc<Inner<T>>,
+    id: Option<usize>,
+}
+
+/// An exclusive borrow of the value in an `AsyncCell`.
+///
+/// The borrow is released, and the next waiting `BorrowMut` woken up, when the
+/// guard is dropped.
+pub struct AsyncCellGuard<T> {
+    inner: Rc<Inner<T>>,
+}
+
+impl<T> AsyncCell<T> {
+    /// Creates a new `AsyncCell` containing `t`.
+    pub fn new(t: T) -> AsyncCell<T> {
+        AsyncCell {
+            inner: Rc::new(Inner {
+                value: UnsafeCell::new(t),
+                state: RefCell::new(State {
+                    borrowed: false,
+                    granted: None,
+                    waiters: VecDeque::new(),
+                    next_id: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Returns a future which resolves to an exclusive borrow of the value.
+    ///
+    /// If the value isn't currently borrowed and nobody else is waiting, the
+    /// returned future resolves on its first poll. Otherwise the current task
+    /// is queued and notified once all borrows queued before it have been
+    /// released.
+    pub fn borrow_mut(&self) -> BorrowMut<T> {
+        BorrowMut {
+            inner: self.inner.clone(),
+            id: None,
+        }
+    }
+
+    /// Returns handles to the tasks waiting to borrow the value, in the order
+    /// they will be granted it.
+    ///
+    /// This is intended for debugging contention on a cell. Waiters are
+    /// identified by their `Task` handles, so `Task::will_notify_current` can be
+    /// used to find out whether the current task is among them. A waiter which
+    /// has been granted the borrow but hasn't picked it up yet is no longer
+    /// considered to be waiting.
+    pub fn waiters(&self) -> Vec<Task> {
+        self.inner.state.borrow().waiters.iter().map(|e| e.1.clone()).collect()
+    }
+}
+
+impl<T> Clone for AsyncCell<T> {
+    fn clone(&self) -> AsyncCell<T> {
+        AsyncCell { inner: self.inner.clone() }
+    }
+}
+
+impl<T> fmt::Debug for AsyncCell<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("AsyncCell")
+            .field("state", &*self.inner.state.borrow())
+            .finish()
+    }
+}
+
+impl<T> Inner<T> {
+    fn release(&self) {
+        let mut state = self.state.borrow_mut();
+        match state.waiters.pop_front() {
+            Some((id, task)) => {
+                state.granted = Some(id);
+                drop(state);
+                task.notify();
+            }
+            None => state.borrowed = false,
+        }
+    }
+}
+
+impl<T> Future for BorrowMut<T> {
+    type Item = AsyncCellGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<AsyncCellGuard<T>, ()> {
+        let mut state = self.inner.state.borrow_mut();
+        match self.id {
+            None if !state.borrowed => {
+                state.borrowed = true;
+            }
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back((id, task::current()));
+                self.id = Some(id);
+                return Ok(Async::NotReady)
+            }
+            Some(id) if state.granted == Some(id) => {
+                state.granted = None;
+                self.id = None;
+            }
+            Some(id) => {
+                // Still waiting, but we may be polled from a different task
+                // than the one we registered with.
+                if let Some(entry) = state.waiters.iter_mut().find(|e| e.0 == id) {
+                    entry.1 = task::current();
+                }
+                return Ok(Async::NotReady)
+            }
+        }
+        Ok(Async::Ready(AsyncCellGuard { inner: self.inner.clone() }))
+    }
+}
+
+impl<T> Drop for BorrowMut<T> {
+    fn drop(&mut self) {
+        let id = match self.id {
+            Some(id) => id,
+            None => return,
+        };
+        let granted = {
+            let mut state = self.inner.state.borrow_mut();
+            if state.granted == Some(id) {
+                state.granted = None;
+                true
+            } else {
+                state.waiters.retain(|e| e.0 != id);
+                false
+            }
+        };
+        // We were handed the borrow but never picked it up, so pass it on.
+        if granted {
+            self.inner.release();
+        }
+    }
+}
+
+impl<T> fmt::Debug for BorrowMut<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BorrowMut")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<T> Deref for AsyncCellGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.inner.value.get() }
+    }
+}
+
+impl<T> DerefMut for AsyncCellGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.inner.value.get() }
+    }
+}
+
+impl<T> Drop for AsyncCellGuard<T> {
+    fn drop(&mut self) {
+        self.inner.release();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AsyncCellGuard<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("AsyncCellGuard")
+            .field(&**self)
+            .finish()
+    }
+}
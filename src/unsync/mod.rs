@@ -3,5 +3,6 @@
 //! This module contains similar abstractions to `sync`, for communications
 //! between tasks on the same thread only.
 
+pub mod cell;
 pub mod mpsc;
 pub mod oneshot;
@@ -0,0 +1,99 @@
+extern crate futures;
+
+use std::error::Error;
+use std::fmt;
+
+use futures::prelude::*;
+use futures::future::{self, MaxPollsError};
+use futures::executor;
+
+mod support;
+use support::*;
+
+#[test]
+fn completes_within_budget() {
+    let mut n = 0;
+    let f = future::poll_fn(move || {
+        n += 1;
+        if n == 3 {
+            Ok::<_, u32>(Async::Ready(n))
+        } else {
+            Ok(Async::NotReady)
+        }
+    });
+    let mut f = executor::spawn(f.max_polls(3));
+    assert!(f.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert!(f.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert_eq!(f.poll_future_notify(&notify_noop(), 0), Ok(Async::Ready(3)));
+}
+
+#[test]
+fn exceeds_budget() {
+    let mut f = executor::spawn(future::empty::<(), u32>().max_polls(2));
+    assert!(f.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert!(f.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert_eq!(f.poll_future_notify(&notify_noop(), 0),
+               Err(MaxPollsError::PollBudgetExceeded));
+}
+
+#[test]
+fn passes_through_errors() {
+    assert_done(|| f_err(3).max_polls(1), Err(MaxPollsError::Inner(3)));
+}
+
+#[test]
+fn zero_budget_never_polls() {
+    let f = future::poll_fn(|| -> Poll<i32, u32> {
+        panic!("should not be polled");
+    });
+    assert_eq!(f.max_polls(0).wait(), Err(MaxPollsError::PollBudgetExceeded));
+}
+
+#[derive(Debug)]
+struct MyError(Option<RootError>);
+
+impl fmt::Display for MyError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "my error")
+    }
+}
+
+impl Error for MyError {
+    fn description(&self) -> &str {
+        "my error"
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        self.0.as_ref().map(|e| e as &Error)
+    }
+}
+
+#[derive(Debug)]
+struct RootError;
+
+impl fmt::Display for RootError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "root error")
+    }
+}
+
+impl Error for RootError {
+    fn description(&self) -> &str {
+        "root error"
+    }
+}
+
+#[test]
+fn inner_error_is_transparent() {
+    let err = future::err::<(), _>(MyError(Some(RootError))).max_polls(1).wait().unwrap_err();
+    assert_eq!(err.to_string(), "my error");
+    assert_eq!(err.description(), "my error");
+    assert_eq!(err.cause().unwrap().to_string(), "root error");
+
+    let err = future::err::<(), _>(MyError(None)).max_polls(1).wait().unwrap_err();
+    assert!(err.cause().is_none());
+
+    let err = future::empty::<(), MyError>().max_polls(0).wait().unwrap_err();
+    assert_eq!(err.to_string(), "poll budget exceeded");
+    assert!(err.cause().is_none());
+}
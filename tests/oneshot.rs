@@ -1,9 +1,12 @@
 extern crate futures;
 
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::thread;
 
 use futures::prelude::*;
+use futures::executor::{self, Notify};
 use futures::future::{lazy, ok};
 use futures::sync::oneshot::*;
 
@@ -120,3 +123,71 @@ fn cancel_sends() {
     drop(tx);
     t.join().unwrap();
 }
+
+#[test]
+fn send_from_other_thread_notifies_once() {
+    struct Count(AtomicUsize);
+
+    impl Notify for Count {
+        fn notify(&self, _id: usize) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let count = Arc::new(Count(AtomicUsize::new(0)));
+    let (tx, rx) = channel::<u32>();
+    let mut rx = executor::spawn(rx);
+    assert!(rx.poll_future_notify(&count, 0).unwrap().is_not_ready());
+
+    thread::spawn(move || tx.send(42).unwrap()).join().unwrap();
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+
+    assert_eq!(rx.poll_future_notify(&count, 0).unwrap(), Async::Ready(42));
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn send_from_other_thread_wakes_wait() {
+    for _ in 0..1000 {
+        let (tx, rx) = channel::<u32>();
+        let t = thread::spawn(move || tx.send(1).unwrap());
+        assert_eq!(rx.wait(), Ok(1));
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn send_while_parked_notifies_once() {
+    struct Park {
+        notified: Mutex<usize>,
+        cvar: Condvar,
+    }
+
+    impl Notify for Park {
+        fn notify(&self, _id: usize) {
+            *self.notified.lock().unwrap() += 1;
+            self.cvar.notify_one();
+        }
+    }
+
+    for _ in 0..1000 {
+        let park = Arc::new(Park {
+            notified: Mutex::new(0),
+            cvar: Condvar::new(),
+        });
+        let (tx, rx) = channel::<u32>();
+        let mut rx = executor::spawn(rx);
+        assert!(rx.poll_future_notify(&park, 0).unwrap().is_not_ready());
+
+        let t = thread::spawn(move || tx.send(42).unwrap());
+        {
+            let mut notified = park.notified.lock().unwrap();
+            while *notified == 0 {
+                notified = park.cvar.wait(notified).unwrap();
+            }
+        }
+        assert_eq!(rx.poll_future_notify(&park, 0).unwrap(), Async::Ready(42));
+        t.join().unwrap();
+        assert_eq!(*park.notified.lock().unwrap(), 1);
+    }
+}
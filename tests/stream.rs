@@ -1,10 +1,13 @@
 #[macro_use]
 extern crate futures;
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use futures::prelude::*;
-use futures::executor;
+use futures::executor::{self, Notify};
 use futures::future::{err, ok};
-use futures::stream::{empty, iter_ok, poll_fn, Peekable};
+use futures::stream::{empty, iter_ok, iter_cooperative, poll_fn, Peekable};
 use futures::sync::oneshot;
 use futures::sync::mpsc;
 
@@ -398,3 +401,50 @@ fn stream_poll_fn() {
 
     assert_eq!(read_stream.wait().count(), 5);
 }
+
+#[test]
+fn iter_cooperative_yields_between_batches() {
+    struct Count(AtomicUsize);
+
+    impl Notify for Count {
+        fn notify(&self, _id: usize) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let count = Arc::new(Count(AtomicUsize::new(0)));
+    let mut s = executor::spawn(iter_cooperative::<_, u32>(vec![1, 2, 3], 2));
+
+    let mut poll = || s.poll_stream_notify(&count, 0);
+    assert_eq!(poll(), Ok(Async::Ready(Some(1))));
+    assert_eq!(poll(), Ok(Async::Ready(Some(2))));
+    assert_eq!(poll(), Ok(Async::NotReady));
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+    assert_eq!(poll(), Ok(Async::Ready(Some(3))));
+    assert_eq!(poll(), Ok(Async::Ready(None)));
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn iter_cooperative_full_last_batch() {
+    let mut s = executor::spawn(iter_cooperative::<_, u32>(vec![1, 2, 3, 4], 2));
+    assert_eq!(s.poll_stream_notify(&notify_panic(), 0), Ok(Async::Ready(Some(1))));
+    assert_eq!(s.poll_stream_notify(&notify_panic(), 0), Ok(Async::Ready(Some(2))));
+    assert_eq!(s.poll_stream_notify(&notify_noop(), 0), Ok(Async::NotReady));
+    assert_eq!(s.poll_stream_notify(&notify_panic(), 0), Ok(Async::Ready(Some(3))));
+    assert_eq!(s.poll_stream_notify(&notify_panic(), 0), Ok(Async::Ready(Some(4))));
+    // The iterator is exhausted, so the stream ends without yielding again.
+    assert_eq!(s.poll_stream_notify(&notify_panic(), 0), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn iter_cooperative_wait() {
+    let s = iter_cooperative::<_, u32>(0..10, 3);
+    assert_eq!(s.collect().wait(), Ok((0..10).collect::<Vec<_>>()));
+}
+
+#[test]
+#[should_panic]
+fn iter_cooperative_zero_batch() {
+    iter_cooperative::<_, u32>(vec![1], 0);
+}
@@ -5,12 +5,15 @@ extern crate futures;
 mod support;
 
 use futures::prelude::*;
+use futures::executor;
+use futures::unsync::cell::AsyncCell;
 use futures::unsync::oneshot;
 use futures::unsync::mpsc::{self, SendError};
 use futures::future::lazy;
 use futures::stream::{iter_ok, unfold};
 
 use support::local_executor::Core;
+use support::notify_noop;
 
 #[test]
 fn mpsc_send_recv() {
@@ -178,3 +181,103 @@ fn spawn_kill_dead_stream() {
         },
     }
 }
+
+#[test]
+fn cell_borrow_mut() {
+    let cell = AsyncCell::new(1);
+    *cell.borrow_mut().wait().unwrap() += 1;
+    assert_eq!(*cell.borrow_mut().wait().unwrap(), 2);
+}
+
+#[test]
+fn cell_waiters_are_fifo() {
+    let cell = AsyncCell::new(Vec::new());
+    let mut a = executor::spawn(cell.borrow_mut());
+    let mut b = executor::spawn(cell.borrow_mut());
+    let mut c = executor::spawn(cell.borrow_mut());
+
+    let guard = match a.poll_future_notify(&notify_noop(), 0).unwrap() {
+        Async::Ready(guard) => guard,
+        Async::NotReady => panic!("cell should not be borrowed"),
+    };
+    assert!(c.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert!(b.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    drop(guard);
+
+    // `c` asked first, so `b` must wait for it, as must a newcomer.
+    let mut d = executor::spawn(cell.borrow_mut());
+    assert!(d.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert!(b.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    match c.poll_future_notify(&notify_noop(), 0).unwrap() {
+        Async::Ready(mut guard) => guard.push('c'),
+        Async::NotReady => panic!("c should have been granted the borrow"),
+    }
+    match b.poll_future_notify(&notify_noop(), 0).unwrap() {
+        Async::Ready(mut guard) => guard.push('b'),
+        Async::NotReady => panic!("b should have been granted the borrow"),
+    }
+    match d.poll_future_notify(&notify_noop(), 0).unwrap() {
+        Async::Ready(guard) => assert_eq!(*guard, ['c', 'b']),
+        Async::NotReady => panic!("d should have been granted the borrow"),
+    }
+}
+
+#[test]
+fn cell_dropped_waiter_passes_borrow_on() {
+    let cell = AsyncCell::new(0);
+    let guard = cell.borrow_mut().wait().unwrap();
+    let mut b = executor::spawn(cell.borrow_mut());
+    let mut c = executor::spawn(cell.borrow_mut());
+    assert!(b.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+    assert!(c.poll_future_notify(&notify_noop(), 0).unwrap().is_not_ready());
+
+    // `b` is granted the borrow but dropped before picking it up.
+    drop(guard);
+    drop(b);
+    assert!(c.poll_future_notify(&notify_noop(), 0).unwrap().is_ready());
+}
+
+#[test]
+fn cell_borrow_held_across_yield() {
+    let core = Core::new();
+    let cell = AsyncCell::new(Vec::new());
+    let (tx, rx) = oneshot::channel::<()>();
+
+    let cell2 = cell.clone();
+    core.spawn(cell.borrow_mut().and_then(move |mut guard| {
+        rx.map(move |()| guard.push(1)).map_err(|_| ())
+    }));
+    core.spawn(cell2.borrow_mut().map(|mut guard| guard.push(2)));
+    core.spawn(lazy(move || tx.send(())));
+
+    let v = core.run(cell.borrow_mut()).unwrap();
+    assert_eq!(*v, [1, 2]);
+}
+
+#[test]
+fn cell_waiters() {
+    let cell = AsyncCell::new(0);
+    assert!(cell.waiters().is_empty());
+
+    let guard = cell.borrow_mut().wait().unwrap();
+    let mut b = executor::spawn(cell.borrow_mut());
+    let mut c = executor::spawn(cell.borrow_mut());
+    assert!(b.poll_future_notify(&notify_noop(), 1).unwrap().is_not_ready());
+    assert!(c.poll_future_notify(&notify_noop(), 2).unwrap().is_not_ready());
+
+    let waiters = cell.waiters();
+    assert_eq!(waiters.len(), 2);
+    let mut check = executor::spawn(lazy(|| {
+        assert!(!waiters[0].will_notify_current());
+        assert!(waiters[1].will_notify_current());
+        Ok::<(), ()>(())
+    }));
+    assert!(check.poll_future_notify(&notify_noop(), 2).unwrap().is_ready());
+
+    // Once granted the borrow, `b` is no longer waiting.
+    drop(guard);
+    assert_eq!(cell.waiters().len(), 1);
+    drop(b);
+    assert!(c.poll_future_notify(&notify_noop(), 2).unwrap().is_ready());
+    assert!(cell.waiters().is_empty());
+}